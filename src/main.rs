@@ -1,54 +1,169 @@
-use blake3;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::env;
 use std::fs::{self, File};
 use std::io::{self, Read};
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use std::time::Instant;
 
+/// Parsed command-line options.
+struct Args {
+    path: PathBuf,
+    recursive: bool,
+    threads: usize,
+    hash_type: HashType,
+    no_cache: bool,
+    remove: Option<RemovePolicy>,
+    dry_run: bool,
+    filters: FileFilters,
+    count_hardlinks: bool,
+    format: OutputFormat,
+}
+
 /// Small CLI:
-/// sorty [PATH] [-r|--recursive]
+/// sorty [PATH] [-r|--recursive] [--threads N] [--hash <blake3|xxh3|crc32>] [--no-cache]
+///       [--remove <keep-newest|keep-oldest|hardlink>] [--dry-run]
+///       [--ext LIST] [--exclude-ext LIST] [--exclude GLOB] [--count-hardlinks]
+///       [--format <text|json>]
 ///
 /// - PATH: directory to scan (defaults to ".")
 /// - -r / --recursive: traverse subdirectories
+/// - --threads N: number of worker threads for the size/hash stages
+///   (defaults to the number of logical cores)
+/// - --hash: digest algorithm for the hash stages (defaults to xxh3)
+/// - --no-cache: bypass the on-disk hash cache
+/// - --remove: act on found duplicates instead of just reporting them
+/// - --dry-run: print what --remove would do without touching the filesystem
+/// - --ext: comma-separated list of extensions to include (others are skipped)
+/// - --exclude-ext: comma-separated list of extensions to skip
+/// - --exclude: glob pattern for paths to skip (repeatable)
+/// - --count-hardlinks: on Unix, report existing hard links as duplicates too
+///   (by default they're collapsed since they already share storage)
+/// - --format: report output format, text or json (defaults to text)
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let (path, recursive) = parse_args()?;
+    let args = parse_args()?;
+
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(args.threads)
+        .build_global()?;
 
     let start = Instant::now();
 
-    let (files, empty_files) = collect_files(&path, recursive)?;
+    let (files, empty_files) = collect_files(&args.path, args.recursive, &args.filters)?;
     if files.is_empty() {
-        println!("No files to process.");
-        if !empty_files.is_empty() {
-            println!("\nEmpty files:");
-            for p in empty_files {
-                println!("  {}", p.display());
+        match args.format {
+            OutputFormat::Text => {
+                println!("No files to process.");
+                if !empty_files.is_empty() {
+                    println!("\nEmpty files:");
+                    for p in empty_files {
+                        println!("  {}", p.display());
+                    }
+                }
             }
+            OutputFormat::Json => print_report_json(&[], &empty_files, start.elapsed()),
         }
         return Ok(());
     }
 
+    // Existing hard links already share storage; don't report them as
+    // duplicates unless the user asked for the raw count.
+    let files = dedupe_hardlinks(files, args.count_hardlinks);
+
     // First group by size to avoid hashing files of unique sizes
     let size_buckets = group_by_size(files);
 
+    let cache = if args.no_cache {
+        None
+    } else {
+        Some(Mutex::new(load_hash_cache()))
+    };
+
     // Now hash only buckets where there are candidates (len > 1)
-    let groups = group_by_hash(size_buckets)?;
+    let groups = group_by_hash(size_buckets, args.hash_type, cache.as_ref())?;
+
+    if let Some(cache) = &cache {
+        if let Ok(cache) = cache.lock() {
+            save_hash_cache(&cache);
+        }
+    }
+
+    let reclaimed_bytes = match args.remove {
+        Some(policy) => Some(apply_removal(&groups, policy, args.dry_run)?),
+        None => None,
+    };
 
     let duration = start.elapsed();
 
-    print_report(&groups, &empty_files, duration);
+    print_report(&groups, &empty_files, duration, reclaimed_bytes, args.format);
 
     Ok(())
 }
 
-fn parse_args() -> Result<(PathBuf, bool), Box<dyn std::error::Error>> {
+fn default_threads() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+fn parse_args() -> Result<Args, Box<dyn std::error::Error>> {
     let mut args = env::args().skip(1);
     let mut path = None;
     let mut recursive = false;
+    let mut threads = default_threads();
+    let mut hash_type = HashType::default();
+    let mut no_cache = false;
+    let mut remove = None;
+    let mut dry_run = false;
+    let mut filters = FileFilters::default();
+    let mut count_hardlinks = false;
+    let mut format = OutputFormat::default();
 
     while let Some(arg) = args.next() {
         match arg.as_str() {
             "-r" | "--recursive" => recursive = true,
+            "--threads" => {
+                let n = args
+                    .next()
+                    .ok_or("--threads requires a value")?
+                    .parse::<usize>()
+                    .map_err(|e| format!("invalid --threads value: {}", e))?;
+                if n == 0 {
+                    return Err("--threads must be at least 1".into());
+                }
+                threads = n;
+            }
+            "--hash" => {
+                let value = args.next().ok_or("--hash requires a value")?;
+                hash_type = value.parse::<HashType>()?;
+            }
+            "--no-cache" => no_cache = true,
+            "--remove" => {
+                let value = args.next().ok_or("--remove requires a value")?;
+                remove = Some(value.parse::<RemovePolicy>()?);
+            }
+            "--dry-run" => dry_run = true,
+            "--ext" => {
+                let value = args.next().ok_or("--ext requires a value")?;
+                filters.include_ext = Some(parse_ext_list(&value));
+            }
+            "--exclude-ext" => {
+                let value = args.next().ok_or("--exclude-ext requires a value")?;
+                filters.exclude_ext.extend(parse_ext_list(&value));
+            }
+            "--exclude" => {
+                let value = args.next().ok_or("--exclude requires a value")?;
+                filters
+                    .exclude_globs
+                    .push(glob::Pattern::new(&value).map_err(|e| e.to_string())?);
+            }
+            "--count-hardlinks" => count_hardlinks = true,
+            "--format" => {
+                let value = args.next().ok_or("--format requires a value")?;
+                format = value.parse::<OutputFormat>()?;
+            }
             "-h" | "--help" => {
                 print_usage_and_exit();
             }
@@ -67,20 +182,105 @@ fn parse_args() -> Result<(PathBuf, bool), Box<dyn std::error::Error>> {
         return Err(format!("Path {:?} does not exist", path).into());
     }
 
-    Ok((path, recursive))
+    if dry_run && remove.is_none() {
+        return Err("--dry-run requires --remove".into());
+    }
+
+    Ok(Args {
+        path,
+        recursive,
+        threads,
+        hash_type,
+        no_cache,
+        remove,
+        dry_run,
+        filters,
+        count_hardlinks,
+        format,
+    })
 }
 
 fn print_usage_and_exit() -> ! {
-    eprintln!("Usage: sorty [PATH] [-r|--recursive]");
+    eprintln!(
+        "Usage: sorty [PATH] [-r|--recursive] [--threads N] [--hash <blake3|xxh3|crc32>] [--no-cache]"
+    );
+    eprintln!("       [--remove <keep-newest|keep-oldest|hardlink>] [--dry-run]");
+    eprintln!("       [--ext LIST] [--exclude-ext LIST] [--exclude GLOB] [--count-hardlinks]");
+    eprintln!("       [--format <text|json>]");
     eprintln!("  PATH: directory to scan (defaults to \".\")");
     eprintln!("  -r, --recursive: traverse subdirectories");
+    eprintln!("  --threads N: worker threads for size/hash stages (default: logical cores)");
+    eprintln!("  --no-cache: bypass the on-disk hash cache");
+    eprintln!("  --hash: digest algorithm for the hash stages (default: xxh3)");
+    eprintln!("  --remove: act on found duplicates instead of just reporting them");
+    eprintln!("  --dry-run: print what --remove would do without touching the filesystem");
+    eprintln!("  --ext: comma-separated extensions to include, e.g. jpg,png,raw");
+    eprintln!("  --exclude-ext: comma-separated extensions to skip");
+    eprintln!("  --exclude: glob pattern for paths to skip (repeatable)");
+    eprintln!("  --count-hardlinks: report existing hard links as duplicates too (Unix only)");
+    eprintln!("  --format: report output format, text or json (default: text)");
     std::process::exit(1);
 }
 
-/// Traverse `path` and collect regular files.
-/// If `recursive` is true, descend into directories recursively.
-/// Returns (files, empty_files).
-fn collect_files(path: &Path, recursive: bool) -> io::Result<(Vec<PathBuf>, Vec<PathBuf>)> {
+/// Extension allow/deny and path-glob rules applied while traversing.
+/// `None` include sets mean "no restriction"; an empty file never reaches
+/// the filters (empty files bypass duplicate detection entirely).
+#[derive(Default)]
+struct FileFilters {
+    include_ext: Option<std::collections::HashSet<String>>,
+    exclude_ext: std::collections::HashSet<String>,
+    exclude_globs: Vec<glob::Pattern>,
+}
+
+impl FileFilters {
+    fn accepts(&self, path: &Path) -> bool {
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase());
+
+        if let Some(include) = &self.include_ext {
+            match &ext {
+                Some(e) if include.contains(e) => {}
+                _ => return false,
+            }
+        }
+
+        if let Some(e) = &ext {
+            if self.exclude_ext.contains(e) {
+                return false;
+            }
+        }
+
+        if self
+            .exclude_globs
+            .iter()
+            .any(|pattern| pattern.matches_path(path))
+        {
+            return false;
+        }
+
+        true
+    }
+}
+
+/// Parse a comma-separated extension list into a lowercase, dot-stripped set.
+fn parse_ext_list(s: &str) -> std::collections::HashSet<String> {
+    s.split(',')
+        .map(|e| e.trim().trim_start_matches('.').to_ascii_lowercase())
+        .filter(|e| !e.is_empty())
+        .collect()
+}
+
+/// Traverse `path` and collect regular files, applying `filters` to decide
+/// which ones are worth hashing. If `recursive` is true, descend into
+/// directories recursively. Returns (files, empty_files) — empty files are
+/// always reported regardless of `filters`, since they're never hashed.
+fn collect_files(
+    path: &Path,
+    recursive: bool,
+    filters: &FileFilters,
+) -> io::Result<(Vec<PathBuf>, Vec<PathBuf>)> {
     let mut files = Vec::new();
     let mut empty_files = Vec::new();
     if path.is_dir() {
@@ -93,14 +293,15 @@ fn collect_files(path: &Path, recursive: bool) -> io::Result<(Vec<PathBuf>, Vec<
             };
             if meta.is_dir() {
                 if recursive {
-                    let (mut sub_files, mut sub_empty) = collect_files(&p, recursive)?;
+                    let (mut sub_files, mut sub_empty) =
+                        collect_files(&p, recursive, filters)?;
                     files.append(&mut sub_files);
                     empty_files.append(&mut sub_empty);
                 }
             } else if meta.is_file() {
                 if meta.len() == 0 {
                     empty_files.push(p);
-                } else {
+                } else if filters.accepts(&p) {
                     files.push(p);
                 }
             } else {
@@ -111,7 +312,7 @@ fn collect_files(path: &Path, recursive: bool) -> io::Result<(Vec<PathBuf>, Vec<
         let meta = fs::metadata(path)?;
         if meta.len() == 0 {
             empty_files.push(path.to_path_buf());
-        } else {
+        } else if filters.accepts(path) {
             files.push(path.to_path_buf());
         }
     } else {
@@ -121,47 +322,323 @@ fn collect_files(path: &Path, recursive: bool) -> io::Result<(Vec<PathBuf>, Vec<
     Ok((files, empty_files))
 }
 
-/// Group files by their size (in bytes).
+/// Collapse paths that are already hard-linked to each other (same device +
+/// inode) down to a single representative path, so sorty doesn't recommend
+/// destructive action on files that already share storage. Pass
+/// `count_hardlinks = true` to disable this and get the raw, unfiltered
+/// count instead.
+#[cfg(target_family = "unix")]
+fn dedupe_hardlinks(files: Vec<PathBuf>, count_hardlinks: bool) -> Vec<PathBuf> {
+    use std::os::unix::fs::MetadataExt;
+
+    if count_hardlinks {
+        return files;
+    }
+
+    let mut seen_inodes: std::collections::HashSet<(u64, u64)> = std::collections::HashSet::new();
+    files
+        .into_iter()
+        .filter(|p| match fs::metadata(p) {
+            Ok(meta) => seen_inodes.insert((meta.dev(), meta.ino())),
+            Err(_) => true, // let downstream stages surface the error
+        })
+        .collect()
+}
+
+#[cfg(not(target_family = "unix"))]
+fn dedupe_hardlinks(files: Vec<PathBuf>, _count_hardlinks: bool) -> Vec<PathBuf> {
+    files
+}
+
+/// Group files by their size (in bytes). Metadata reads run in parallel
+/// across the configured thread pool; the results are folded into a single
+/// map sequentially to avoid lock contention on a shared `HashMap`.
 fn group_by_size(files: Vec<PathBuf>) -> HashMap<u64, Vec<PathBuf>> {
+    let sized: Vec<(u64, PathBuf)> = files
+        .into_par_iter()
+        .filter_map(|p| fs::metadata(&p).ok().map(|meta| (meta.len(), p)))
+        .collect();
+
     let mut map: HashMap<u64, Vec<PathBuf>> = HashMap::new();
-    for p in files {
-        if let Ok(meta) = fs::metadata(&p) {
-            let size = meta.len();
-            map.entry(size).or_default().push(p);
-        }
+    for (size, p) in sized {
+        map.entry(size).or_default().push(p);
     }
     map
 }
 
-/// For each size bucket that has more than one file, compute blake3 hash (streamed)
-/// and group by hash. Returns a Vec of groups (each group is Vec<PathBuf>) where len > 1.
+/// Number of leading bytes read during the partial-hash stage.
+const PARTIAL_HASH_BLOCK_SIZE: usize = 4096;
+
+/// Which stage a hash was computed for. Partial hashes are a cheap pre-filter
+/// over the first `PARTIAL_HASH_BLOCK_SIZE` bytes; only survivors go on to a
+/// Full hash over the whole file.
+enum HashMode {
+    Partial,
+    Full,
+}
+
+impl HashMode {
+    /// Cap on bytes read for this mode, or `None` to read the whole file.
+    fn limit(&self) -> Option<usize> {
+        match self {
+            HashMode::Partial => Some(PARTIAL_HASH_BLOCK_SIZE),
+            HashMode::Full => None,
+        }
+    }
+}
+
+/// Selectable digest algorithm used for the partial and full hash stages.
+/// `Xxh3` is the default: it's a non-cryptographic hash, fast enough to make
+/// the hashing stage IO-bound, and perfectly adequate for same-size
+/// duplicate detection. `Blake3` is offered for users who want collision
+/// resistance, and `Crc32` for the cheapest possible check.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+enum HashType {
+    Blake3,
+    #[default]
+    Xxh3,
+    Crc32,
+}
+
+impl std::str::FromStr for HashType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "blake3" => Ok(HashType::Blake3),
+            "xxh3" => Ok(HashType::Xxh3),
+            "crc32" => Ok(HashType::Crc32),
+            other => Err(format!(
+                "unknown hash algorithm {:?} (expected blake3, xxh3, or crc32)",
+                other
+            )),
+        }
+    }
+}
+
+impl HashType {
+    fn new_hasher(self) -> Box<dyn FileHasher> {
+        match self {
+            HashType::Blake3 => Box::new(blake3::Hasher::new()),
+            HashType::Xxh3 => Box::new(xxhash_rust::xxh3::Xxh3::new()),
+            HashType::Crc32 => Box::new(crc32fast::Hasher::new()),
+        }
+    }
+}
+
+/// A streaming digest. Abstracts over the concrete hasher so `hash_file`
+/// and `hash_file_prefix` don't need to care which `HashType` was selected.
+trait FileHasher {
+    fn update(&mut self, data: &[u8]);
+    fn finish(self: Box<Self>) -> Vec<u8>;
+}
+
+impl FileHasher for blake3::Hasher {
+    fn update(&mut self, data: &[u8]) {
+        blake3::Hasher::update(self, data);
+    }
+
+    fn finish(self: Box<Self>) -> Vec<u8> {
+        self.finalize().as_bytes().to_vec()
+    }
+}
+
+impl FileHasher for xxhash_rust::xxh3::Xxh3 {
+    fn update(&mut self, data: &[u8]) {
+        xxhash_rust::xxh3::Xxh3::update(self, data);
+    }
+
+    fn finish(self: Box<Self>) -> Vec<u8> {
+        self.digest128().to_be_bytes().to_vec()
+    }
+}
+
+impl FileHasher for crc32fast::Hasher {
+    fn update(&mut self, data: &[u8]) {
+        crc32fast::Hasher::update(self, data);
+    }
+
+    fn finish(self: Box<Self>) -> Vec<u8> {
+        self.finalize().to_be_bytes().to_vec()
+    }
+}
+
+/// An entry in the on-disk hash cache: the file's full-hash digest, valid
+/// only as long as size and modification time haven't changed.
+#[derive(Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    size: u64,
+    modified: u64, // seconds since UNIX_EPOCH
+    hash_type: HashType,
+    digest: Vec<u8>,
+}
+
+impl CacheEntry {
+    /// Returns the cached digest if `meta` still matches the size, mtime,
+    /// and hash algorithm this entry was computed with.
+    fn matches(&self, meta: &fs::Metadata, hash_type: HashType) -> Option<Vec<u8>> {
+        if self.hash_type != hash_type || self.size != meta.len() {
+            return None;
+        }
+        if modified_secs(meta)? != self.modified {
+            return None;
+        }
+        Some(self.digest.clone())
+    }
+}
+
+fn modified_secs(meta: &fs::Metadata) -> Option<u64> {
+    meta.modified()
+        .ok()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_secs())
+}
+
+type HashCache = HashMap<PathBuf, CacheEntry>;
+
+/// Where the on-disk hash cache lives: `<platform cache dir>/sorty/hash_cache.json`.
+fn cache_file_path() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("sorty").join("hash_cache.json"))
+}
+
+/// Load the hash cache from disk, defaulting to empty if it's missing or
+/// unreadable (e.g. a stale format from an older sorty version).
+fn load_hash_cache() -> HashCache {
+    let Some(path) = cache_file_path() else {
+        return HashCache::new();
+    };
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+/// Persist the hash cache to disk, creating the cache directory if needed.
+fn save_hash_cache(cache: &HashCache) {
+    let Some(path) = cache_file_path() else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        if fs::create_dir_all(parent).is_err() {
+            return;
+        }
+    }
+    if let Ok(json) = serde_json::to_string(cache) {
+        let _ = fs::write(path, json);
+    }
+}
+
+/// A group of files that share both size and full hash digest.
+struct DupGroup {
+    size: u64,
+    hash: Vec<u8>,
+    paths: Vec<PathBuf>,
+}
+
+/// For each size bucket that has more than one file, first compute a cheap
+/// partial hash over the leading `PARTIAL_HASH_BLOCK_SIZE` bytes and discard
+/// any sub-group that doesn't survive (size + partial already distinguishes
+/// them). Only the survivors get the full hash, reusing the on-disk cache
+/// when a file's size and modification time haven't changed since the last
+/// run. Returns a Vec of groups with length > 1.
 fn group_by_hash(
     size_buckets: HashMap<u64, Vec<PathBuf>>,
-) -> Result<Vec<Vec<PathBuf>>, Box<dyn std::error::Error>> {
-    let mut groups: Vec<Vec<PathBuf>> = Vec::new();
+    hash_type: HashType,
+    cache: Option<&Mutex<HashCache>>,
+) -> Result<Vec<DupGroup>, Box<dyn std::error::Error>> {
+    let mut groups: Vec<DupGroup> = Vec::new();
 
-    for (_size, bucket) in size_buckets.into_iter() {
+    for (size, bucket) in size_buckets.into_iter() {
         if bucket.len() <= 1 {
             continue; // unique size -> cannot be duplicate
         }
 
-        // map hash -> files with that hash (within the same size)
-        let mut hash_map: HashMap<blake3::Hash, Vec<PathBuf>> = HashMap::new();
-        for p in bucket {
-            match hash_file(&p) {
-                Ok(h) => {
-                    hash_map.entry(h).or_default().push(p);
+        // Files smaller than the partial block would read the same bytes
+        // twice (size + partial == full), so skip straight to full hashing.
+        let candidates = if size as usize <= PARTIAL_HASH_BLOCK_SIZE {
+            bucket
+        } else {
+            let partial_pairs: Vec<(PathBuf, Vec<u8>)> = bucket
+                .into_par_iter()
+                .filter_map(
+                    |p| match hash_file_mode(&p, HashMode::Partial, hash_type) {
+                        Ok(h) => Some((p, h)),
+                        Err(e) => {
+                            eprintln!("Warning: failed to hash {}: {}", p.display(), e);
+                            None
+                        }
+                    },
+                )
+                .collect();
+
+            let mut partial_map: HashMap<Vec<u8>, Vec<PathBuf>> = HashMap::new();
+            for (p, h) in partial_pairs {
+                partial_map.entry(h).or_default().push(p);
+            }
+            partial_map
+                .into_values()
+                .filter(|v| v.len() > 1)
+                .flatten()
+                .collect()
+        };
+
+        // Hash survivors in parallel, consulting the on-disk cache first,
+        // then fold the (path, hash) pairs into a single map sequentially
+        // to avoid lock contention.
+        let full_pairs: Vec<(PathBuf, Vec<u8>)> = candidates
+            .into_par_iter()
+            .filter_map(|p| {
+                let meta = fs::metadata(&p).ok();
+
+                if let (Some(cache), Some(meta)) = (cache, &meta) {
+                    if let Some(digest) = cache
+                        .lock()
+                        .unwrap()
+                        .get(&p)
+                        .and_then(|entry| entry.matches(meta, hash_type))
+                    {
+                        return Some((p, digest));
+                    }
                 }
-                Err(e) => {
-                    eprintln!("Warning: failed to hash {}: {}", p.display(), e);
-                    // skip unreadable file
+
+                match hash_file(&p, hash_type) {
+                    Ok(digest) => {
+                        if let (Some(cache), Some(meta)) = (cache, &meta) {
+                            if let Some(modified) = modified_secs(meta) {
+                                cache.lock().unwrap().insert(
+                                    p.clone(),
+                                    CacheEntry {
+                                        size: meta.len(),
+                                        modified,
+                                        hash_type,
+                                        digest: digest.clone(),
+                                    },
+                                );
+                            }
+                        }
+                        Some((p, digest))
+                    }
+                    Err(e) => {
+                        eprintln!("Warning: failed to hash {}: {}", p.display(), e);
+                        None
+                    }
                 }
-            }
+            })
+            .collect();
+
+        let mut hash_map: HashMap<Vec<u8>, Vec<PathBuf>> = HashMap::new();
+        for (p, h) in full_pairs {
+            hash_map.entry(h).or_default().push(p);
         }
 
-        for (_h, v) in hash_map {
-            if v.len() > 1 {
-                groups.push(v);
+        for (h, paths) in hash_map {
+            if paths.len() > 1 {
+                groups.push(DupGroup {
+                    size,
+                    hash: h,
+                    paths,
+                });
             }
         }
     }
@@ -170,29 +647,212 @@ fn group_by_hash(
 }
 
 /// Stream-hash a file using a buffer to avoid loading it entirely into memory.
-fn hash_file(path: &Path) -> io::Result<blake3::Hash> {
+fn hash_file(path: &Path, hash_type: HashType) -> io::Result<Vec<u8>> {
+    hash_file_mode(path, HashMode::Full, hash_type)
+}
+
+/// Hash at most `n` leading bytes of `path`. Used as a cheap pre-filter
+/// (see `HashMode::Partial`) before committing to a full-file hash.
+fn hash_file_prefix(path: &Path, n: usize, hash_type: HashType) -> io::Result<Vec<u8>> {
     let mut file = File::open(path)?;
-    let mut hasher = blake3::Hasher::new();
+    let mut hasher = hash_type.new_hasher();
     let mut buf = [0u8; 8192];
-    loop {
-        let n = file.read(&mut buf)?;
-        if n == 0 {
+    let mut remaining = n;
+    while remaining > 0 {
+        let to_read = remaining.min(buf.len());
+        let got = file.read(&mut buf[..to_read])?;
+        if got == 0 {
             break;
         }
-        hasher.update(&buf[..n]);
+        hasher.update(&buf[..got]);
+        remaining -= got;
+    }
+    Ok(hasher.finish())
+}
+
+fn hash_file_mode(path: &Path, mode: HashMode, hash_type: HashType) -> io::Result<Vec<u8>> {
+    match mode.limit() {
+        Some(n) => hash_file_prefix(path, n, hash_type),
+        None => {
+            let mut file = File::open(path)?;
+            let mut hasher = hash_type.new_hasher();
+            let mut buf = [0u8; 8192];
+            loop {
+                let n = file.read(&mut buf)?;
+                if n == 0 {
+                    break;
+                }
+                hasher.update(&buf[..n]);
+            }
+            Ok(hasher.finish())
+        }
+    }
+}
+
+/// What to do with the duplicates once a group has been found.
+#[derive(Clone, Copy, Debug)]
+enum RemovePolicy {
+    KeepNewest,
+    KeepOldest,
+    Hardlink,
+}
+
+impl std::str::FromStr for RemovePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "keep-newest" => Ok(RemovePolicy::KeepNewest),
+            "keep-oldest" => Ok(RemovePolicy::KeepOldest),
+            "hardlink" => Ok(RemovePolicy::Hardlink),
+            other => Err(format!(
+                "unknown --remove policy {:?} (expected keep-newest, keep-oldest, or hardlink)",
+                other
+            )),
+        }
+    }
+}
+
+/// Pick which file in a duplicate group to keep. `KeepNewest`/`KeepOldest`
+/// compare modification times; `Hardlink` keeps the first file in the
+/// group (the one `print_report` labels "original") and relinks the rest
+/// to it.
+fn pick_keeper(group: &[PathBuf], policy: RemovePolicy) -> io::Result<usize> {
+    match policy {
+        RemovePolicy::Hardlink => Ok(0),
+        RemovePolicy::KeepNewest | RemovePolicy::KeepOldest => {
+            let mut best = 0;
+            let mut best_time = fs::metadata(&group[0])?.modified()?;
+            for (i, p) in group.iter().enumerate().skip(1) {
+                let time = fs::metadata(p)?.modified()?;
+                let better = match policy {
+                    RemovePolicy::KeepNewest => time > best_time,
+                    RemovePolicy::KeepOldest => time < best_time,
+                    RemovePolicy::Hardlink => unreachable!(),
+                };
+                if better {
+                    best = i;
+                    best_time = time;
+                }
+            }
+            Ok(best)
+        }
+    }
+}
+
+/// Apply `policy` to every duplicate group, either deleting the losers
+/// (`keep-newest`/`keep-oldest`) or hard-linking them to the keeper
+/// (`hardlink`). When `dry_run` is true, nothing on disk is touched and
+/// only the actions that would be taken are printed. Returns the number of
+/// bytes reclaimed (or that would be reclaimed, in a dry run).
+fn apply_removal(groups: &[DupGroup], policy: RemovePolicy, dry_run: bool) -> io::Result<u64> {
+    let mut reclaimed = 0u64;
+
+    for group in groups {
+        let group = &group.paths;
+        let keeper_idx = pick_keeper(group, policy)?;
+        let keeper = &group[keeper_idx];
+
+        for (i, dup) in group.iter().enumerate() {
+            if i == keeper_idx {
+                continue;
+            }
+            let size = fs::metadata(dup)?.len();
+
+            match policy {
+                RemovePolicy::KeepNewest | RemovePolicy::KeepOldest => {
+                    if dry_run {
+                        println!("Would delete {} (keeping {})", dup.display(), keeper.display());
+                    } else {
+                        fs::remove_file(dup)?;
+                        println!("Deleted {} (kept {})", dup.display(), keeper.display());
+                    }
+                }
+                RemovePolicy::Hardlink => {
+                    if dry_run {
+                        println!("Would hardlink {} -> {}", dup.display(), keeper.display());
+                    } else {
+                        hardlink_replace(keeper, dup)?;
+                        println!("Hardlinked {} -> {}", dup.display(), keeper.display());
+                    }
+                }
+            }
+
+            reclaimed += size;
+        }
+    }
+
+    Ok(reclaimed)
+}
+
+/// Replace `dup` with a hard link to `keeper`. Links to a temporary name
+/// first and renames it over `dup` so a crash never leaves `dup` missing;
+/// the temp link is cleaned up if the rename fails.
+fn hardlink_replace(keeper: &Path, dup: &Path) -> io::Result<()> {
+    let tmp = dup.with_extension("sorty-tmp-hardlink");
+    fs::hard_link(keeper, &tmp)?;
+    if let Err(e) = fs::rename(&tmp, dup) {
+        let _ = fs::remove_file(&tmp);
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Output format selected by `--format`.
+#[derive(Clone, Copy, Debug, Default)]
+enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            other => Err(format!(
+                "unknown --format value {:?} (expected text or json)",
+                other
+            )),
+        }
     }
-    Ok(hasher.finalize())
 }
 
-/// Print a human-friendly report:
-/// - number of duplicate groups
-/// - total duplicate files
-/// - list groups with original + duplicates
-/// - list empty files
-/// - time elapsed
-fn print_report(groups: &[Vec<PathBuf>], empty_files: &[PathBuf], duration: std::time::Duration) {
+/// Bytes that could be reclaimed by keeping one copy of each duplicate group.
+fn wasted_bytes(groups: &[DupGroup]) -> u64 {
+    groups
+        .iter()
+        .map(|g| g.size * (g.paths.len() as u64 - 1))
+        .sum()
+}
+
+/// Print the report in the format requested by `--format`:
+/// - `text`: human-friendly (groups, empty files, reclaimed bytes, elapsed time)
+/// - `json`: stable schema for piping into other tools (see `JsonReport`)
+fn print_report(
+    groups: &[DupGroup],
+    empty_files: &[PathBuf],
+    duration: std::time::Duration,
+    reclaimed_bytes: Option<u64>,
+    format: OutputFormat,
+) {
+    match format {
+        OutputFormat::Text => print_report_text(groups, empty_files, duration, reclaimed_bytes),
+        OutputFormat::Json => print_report_json(groups, empty_files, duration),
+    }
+}
+
+fn print_report_text(
+    groups: &[DupGroup],
+    empty_files: &[PathBuf],
+    duration: std::time::Duration,
+    reclaimed_bytes: Option<u64>,
+) {
     let group_count = groups.len();
-    let total_dup_files: usize = groups.iter().map(|g| g.len()).sum();
+    let total_dup_files: usize = groups.iter().map(|g| g.paths.len()).sum();
 
     println!("Report:");
     println!(
@@ -201,8 +861,8 @@ fn print_report(groups: &[Vec<PathBuf>], empty_files: &[PathBuf], duration: std:
     );
 
     for (i, group) in groups.iter().enumerate() {
-        println!("\nGroup {} ({} files):", i + 1, group.len());
-        for (j, p) in group.iter().enumerate() {
+        println!("\nGroup {} ({} files):", i + 1, group.paths.len());
+        for (j, p) in group.paths.iter().enumerate() {
             if j == 0 {
                 println!("  original:  {}", p.display());
             } else {
@@ -220,7 +880,57 @@ fn print_report(groups: &[Vec<PathBuf>], empty_files: &[PathBuf], duration: std:
         }
     }
 
+    if let Some(bytes) = reclaimed_bytes {
+        println!("\nReclaimed {} byte(s)", bytes);
+    }
+
     let secs = duration.as_secs();
     let millis = duration.subsec_millis();
     println!("\nElapsed: {}.{:03} s", secs, millis);
 }
+
+/// One duplicate group as it appears in the JSON report.
+#[derive(Serialize)]
+struct JsonGroup {
+    size: u64,
+    hash: String,
+    paths: Vec<PathBuf>,
+}
+
+/// Stable JSON schema emitted by `--format json`.
+#[derive(Serialize)]
+struct JsonReport {
+    duplicate_groups: usize,
+    total_duplicate_files: usize,
+    wasted_bytes: u64,
+    elapsed_ms: u128,
+    groups: Vec<JsonGroup>,
+    empty_files: Vec<PathBuf>,
+}
+
+fn print_report_json(groups: &[DupGroup], empty_files: &[PathBuf], duration: std::time::Duration) {
+    let report = JsonReport {
+        duplicate_groups: groups.len(),
+        total_duplicate_files: groups.iter().map(|g| g.paths.len()).sum(),
+        wasted_bytes: wasted_bytes(groups),
+        elapsed_ms: duration.as_millis(),
+        groups: groups
+            .iter()
+            .map(|g| JsonGroup {
+                size: g.size,
+                hash: hex_encode(&g.hash),
+                paths: g.paths.clone(),
+            })
+            .collect(),
+        empty_files: empty_files.to_vec(),
+    };
+
+    match serde_json::to_string_pretty(&report) {
+        Ok(json) => println!("{}", json),
+        Err(e) => eprintln!("Warning: failed to serialize report: {}", e),
+    }
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}